@@ -1,3 +1,4 @@
+use alloc::format;
 use asr::{Address, FromEndian, Process};
 use bytemuck::CheckedBitPattern;
 
@@ -17,3 +18,30 @@ pub(crate) fn read_host_path<T: CheckedBitPattern>(
 
     process.read::<T>(address + last).ok()
 }
+
+/// Same as [`read_host_path`], but logs through the host's log on the first failure of a path
+/// (latched in `already_failed`) instead of spamming it every tick.
+pub(crate) fn read_host_path_debug<T: CheckedBitPattern>(
+    process: &Process,
+    base_address: Address,
+    offsets: &[u32],
+    debug: bool,
+    already_failed: &mut bool,
+) -> Option<T> {
+    let result = read_host_path::<T>(process, base_address, offsets);
+
+    if debug {
+        match (result.is_some(), *already_failed) {
+            (false, false) => {
+                asr::print_message(&format!(
+                    "read_host_path: failed to resolve offset path {offsets:?}"
+                ));
+                *already_failed = true;
+            }
+            (true, true) => *already_failed = false,
+            _ => (),
+        }
+    }
+
+    result
+}
@@ -8,6 +8,9 @@
     rust_2018_idioms
 )]
 
+extern crate alloc;
+
+use alloc::format;
 use asr::{
     future::{next_tick, retry},
     settings::Gui,
@@ -35,6 +38,20 @@ async fn main() {
                 // Once the target has been found and attached to, set up some default watchers
                 let mut watchers = Watchers::default();
 
+                // Reattaching mid-run: reload the accumulated buffer and seed the stage
+                // watcher so the first tick's transition is detected against the real stage
+                if [TimerState::Running, TimerState::Paused].contains(&timer::state()) {
+                    watchers.active_attempt = ActiveAttempt::load();
+                    if let ActiveAttempt::Running {
+                        buffer,
+                        stage_index,
+                    } = watchers.active_attempt
+                    {
+                        watchers.igt_buffer = buffer;
+                        watchers.stage.update_infallible(stage_index);
+                    }
+                }
+
                 // Perform memory scanning to look for the addresses we need
                 let memory = Memory::init(&process, process_name).await;
 
@@ -46,7 +63,7 @@ async fn main() {
                     // 3. If reset does not return true, then the split action will be run.
                     // 4. If the timer is currently not running (and not paused), then the start action will be run.
                     settings.update();
-                    update_loop(&process, &memory, &mut watchers);
+                    update_loop(&process, &memory, &mut watchers, &settings);
 
                     if [TimerState::Running, TimerState::Paused].contains(&timer::state()) {
                         match is_loading(&watchers, &settings) {
@@ -95,6 +112,21 @@ struct Settings {
     /// Use IGT instead of LRT
     #[default = false]
     igt: bool,
+    /// Start the timer automatically when leaving the world map
+    #[default = true]
+    auto_start: bool,
+    /// Split automatically on every stage change
+    #[default = true]
+    auto_split_on_stage: bool,
+    /// Reset the timer automatically on returning to the world map / title
+    #[default = true]
+    auto_reset: bool,
+    /// Log diagnostics about pointer-path resolution and watcher transitions
+    #[default = false]
+    debug: bool,
+    /// Individual Level mode: time only the active stage instead of the whole run
+    #[default = false]
+    il_mode: bool,
 }
 
 #[derive(Default)]
@@ -102,6 +134,79 @@ struct Watchers {
     is_loading: Watcher<bool>,
     igt: Watcher<Duration>,
     igt_buffer: Duration,
+    stage: Watcher<u8>,
+    active_attempt: ActiveAttempt,
+    debug_latches: DebugLatches,
+}
+
+/// Per `read_host_path` call site in [`update_loop`]: whether its last read already failed
+#[derive(Default)]
+struct DebugLatches {
+    loading_state: bool,
+    is_loading: bool,
+    stage: bool,
+    igt: bool,
+}
+
+/// The current attempt's state, persisted across reattaches via `asr::settings::Map`
+#[derive(Copy, Clone, Default)]
+enum ActiveAttempt {
+    #[default]
+    NotRunning,
+    Running {
+        buffer: Duration,
+        stage_index: u8,
+    },
+    Ended {
+        buffer: Duration,
+        stage_index: u8,
+    },
+}
+
+impl ActiveAttempt {
+    fn load() -> Self {
+        let settings_map = asr::settings::Map::load();
+
+        let buffer = settings_map
+            .get("active_attempt_buffer_ms")
+            .and_then(|val| val.get_i64())
+            .map(Duration::milliseconds)
+            .unwrap_or(Duration::ZERO);
+
+        let stage_index = settings_map
+            .get("active_attempt_stage_index")
+            .and_then(|val| val.get_i64())
+            .map(|val| val as u8)
+            .unwrap_or_default();
+
+        match settings_map
+            .get("active_attempt_state")
+            .and_then(|val| val.get_string())
+            .as_deref()
+        {
+            Some("running") => Self::Running { buffer, stage_index },
+            Some("ended") => Self::Ended { buffer, stage_index },
+            _ => Self::NotRunning,
+        }
+    }
+
+    fn store(&self) {
+        let settings_map = asr::settings::Map::load();
+
+        let (state, buffer, stage_index) = match *self {
+            Self::NotRunning => ("not_running", Duration::ZERO, 0),
+            Self::Running { buffer, stage_index } => ("running", buffer, stage_index),
+            Self::Ended { buffer, stage_index } => ("ended", buffer, stage_index),
+        };
+
+        settings_map.insert("active_attempt_state", state);
+        settings_map.insert(
+            "active_attempt_buffer_ms",
+            buffer.whole_milliseconds() as i64,
+        );
+        settings_map.insert("active_attempt_stage_index", stage_index as i64);
+        settings_map.store();
+    }
 }
 
 struct Memory {
@@ -141,68 +246,172 @@ async fn hook_process() -> (&'static str, Process) {
     .await
 }
 
-fn update_loop(game: &Process, memory: &Memory, watchers: &mut Watchers) {
+fn update_loop(game: &Process, memory: &Memory, watchers: &mut Watchers, settings: &Settings) {
     // Loading state represent the current status of the loading screen
-    let loading_state = client_layer::read_host_path::<u32>(
+    let loading_state = client_layer::read_host_path_debug::<u32>(
         game,
         memory.base_client_ptr,
         &[0x833678A0, 0x4, 0xE0, 0x13C],
+        settings.debug,
+        &mut watchers.debug_latches.loading_state,
     )
     .unwrap_or_default()
     .from_be();
 
     // This shows whether the game is effectively stuck in a loading state, regardless of the laoding screen shown
-    let is_loading =
-        client_layer::read_host_path::<u8>(game, memory.base_client_ptr, &[0x83367A4C])
-            .map(|val| val != 0)
-            .unwrap_or(false);
+    let is_loading = client_layer::read_host_path_debug::<u8>(
+        game,
+        memory.base_client_ptr,
+        &[0x83367A4C],
+        settings.debug,
+        &mut watchers.debug_latches.is_loading,
+    )
+    .map(|val| val != 0)
+    .unwrap_or(false);
 
+    let was_loading = watchers.is_loading.pair.map(|val| val.current);
     watchers
         .is_loading
         .update_infallible(is_loading || (loading_state != 0 && loading_state != 2));
 
+    if settings.debug {
+        if let Some(was_loading) = was_loading {
+            if was_loading != watchers.is_loading.pair.unwrap().current {
+                asr::print_message(&format!(
+                    "is_loading: {was_loading} -> {}",
+                    watchers.is_loading.pair.unwrap().current
+                ));
+            }
+        }
+    }
+
     // We want to store the internal ID of the current level. In reality we are just checking this for the world map (which should return an empty string)
-    let stage = client_layer::read_host_path::<u8>(
+    let stage = client_layer::read_host_path_debug::<u8>(
         game,
         memory.base_client_ptr,
         &[0x83367900, 0x8, 0xAC, 0x0],
+        settings.debug,
+        &mut watchers.debug_latches.stage,
     )
     .unwrap_or_default();
 
     let igt = if stage == 0 {
         Duration::ZERO
     } else {
-        client_layer::read_host_path::<f32>(game, memory.base_client_ptr, &[0x83367900, 0x8, 0x5C])
-            .map(|val| val.from_be())
-            .map(|val| {
-                if val.is_nan() || val < 0.0 {
-                    Duration::ZERO
-                } else {
-                    Duration::milliseconds((val * 100.0) as i64 * 10)
-                }
-            })
-            .unwrap_or_default()
+        client_layer::read_host_path_debug::<f32>(
+            game,
+            memory.base_client_ptr,
+            &[0x83367900, 0x8, 0x5C],
+            settings.debug,
+            &mut watchers.debug_latches.igt,
+        )
+        .map(|val| val.from_be())
+        .map(|val| {
+            if val.is_nan() || val < 0.0 {
+                Duration::ZERO
+            } else {
+                Duration::milliseconds((val * 100.0) as i64 * 10)
+            }
+        })
+        .unwrap_or_default()
     };
 
     let old_igt = watchers.igt.pair.map(|val| val.current).unwrap_or_default();
+    let old_stage = watchers.stage.pair.map(|val| val.current);
 
     if igt < old_igt {
-        watchers.igt_buffer += old_igt;
+        if settings.il_mode {
+            watchers.igt_buffer = Duration::ZERO;
+        } else {
+            watchers.igt_buffer += old_igt;
+        }
+
+        if settings.debug {
+            asr::print_message(&format!(
+                "igt reset from {old_igt:?}, buffer now {:?}",
+                watchers.igt_buffer
+            ));
+        }
+    }
+
+    if settings.debug {
+        if let Some(old_stage) = old_stage {
+            if old_stage != stage {
+                asr::print_message(&format!("stage: {old_stage} -> {stage}"));
+            }
+        }
     }
 
     watchers.igt.update_infallible(igt);
+    watchers.stage.update_infallible(stage);
+
+    watchers.active_attempt = match timer::state() {
+        TimerState::Running | TimerState::Paused => ActiveAttempt::Running {
+            buffer: watchers.igt_buffer,
+            stage_index: stage,
+        },
+        TimerState::Ended => ActiveAttempt::Ended {
+            buffer: watchers.igt_buffer,
+            stage_index: stage,
+        },
+        _ => ActiveAttempt::NotRunning,
+    };
+    watchers.active_attempt.store();
 }
 
-fn start(_watchers: &Watchers, _settings: &Settings) -> bool {
-    false
+fn start(watchers: &Watchers, settings: &Settings) -> bool {
+    if !settings.auto_start {
+        return false;
+    }
+
+    let Some(stage) = watchers.stage.pair else {
+        return false;
+    };
+
+    // In IL mode, a run consists of a single stage, so it starts on entering any stage
+    if settings.il_mode {
+        return stage.changed() && stage.current != 0;
+    }
+
+    let Some(igt) = watchers.igt.pair else {
+        return false;
+    };
+
+    igt.changed() && igt.old == Duration::ZERO && igt.current > Duration::ZERO && stage.current != 0
 }
 
-fn split(_watchers: &Watchers, _settings: &Settings) -> bool {
-    false
+fn split(watchers: &Watchers, settings: &Settings) -> bool {
+    // IL runs only ever cover a single stage, so there's nothing to split to
+    if !settings.auto_split_on_stage || settings.il_mode {
+        return false;
+    }
+
+    let Some(stage) = watchers.stage.pair else {
+        return false;
+    };
+
+    stage.changed() && stage.current != 0
 }
 
-fn reset(_watchers: &Watchers, _settings: &Settings) -> bool {
-    false
+fn reset(watchers: &Watchers, settings: &Settings) -> bool {
+    if !settings.auto_reset {
+        return false;
+    }
+
+    let Some(stage) = watchers.stage.pair else {
+        return false;
+    };
+
+    // In IL mode, leaving the stage the attempt started on (to anywhere) ends the attempt
+    if settings.il_mode {
+        return stage.changed();
+    }
+
+    let Some(igt) = watchers.igt.pair else {
+        return false;
+    };
+
+    stage.changed() && stage.current == 0 && igt.current == Duration::ZERO
 }
 
 fn is_loading(watchers: &Watchers, settings: &Settings) -> Option<bool> {